@@ -0,0 +1,158 @@
+//! Generic data structure deserialization framework.
+//!
+//! The two most important traits in this module are `Deserialize` and
+//! `Deserializer`.
+//!
+//!  - **A type that implements `Deserialize` is a data structure** that can
+//!    be deserialized from any data format supported by Serde, and
+//!    conversely
+//!  - **A type that implements `Deserializer` is a data format** that can
+//!    deserialize any data structure supported by Serde.
+
+use lib::*;
+
+///////////////////////////////////////////////////////////////////////////
+
+/// The `Error` trait allows `Deserialize` implementations to create
+/// descriptive error messages belonging to the `Deserializer` against
+/// which they are currently running.
+pub trait Error: Sized {
+    /// Raised when there is general error when deserializing a type.
+    fn custom<T: Display>(msg: T) -> Self;
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// A **data structure** that can be deserialized from any data format
+/// supported by Serde.
+pub trait Deserialize<'de>: Sized {
+    /// Deserialize this value from the given Serde deserializer.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de>;
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// A **data format** that can deserialize any data structure supported by
+/// Serde.
+pub trait Deserializer<'de>: Sized {
+    /// The error type that can be returned if some error occurs during
+    /// deserialization.
+    type Error: Error;
+
+    /// Require the `Deserializer` to figure out how to drive the visitor
+    /// based on what data type is in the input.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a sequence of values.
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a homogeneous sequence
+    /// of `i8` values, analogous to a typed `ByteArray` in formats like NBT.
+    ///
+    /// The default implementation falls back to `deserialize_seq`, which is
+    /// correct for every format but does not take advantage of the typed
+    /// hint.
+    fn deserialize_i8_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    /// Hint that the `Deserialize` type is expecting a homogeneous sequence
+    /// of `i32` values, analogous to a typed `IntArray` in formats like NBT.
+    ///
+    /// See `deserialize_i8_seq` for the default implementation and when to
+    /// override it.
+    fn deserialize_i32_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    /// Hint that the `Deserialize` type is expecting a homogeneous sequence
+    /// of `i64` values, analogous to a typed `LongArray` in formats like NBT.
+    ///
+    /// See `deserialize_i8_seq` for the default implementation and when to
+    /// override it.
+    fn deserialize_i64_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+    {
+        self.deserialize_seq(visitor)
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// This trait represents a visitor that walks through a deserializer.
+pub trait Visitor<'de>: Sized {
+    /// The value produced by this visitor.
+    type Value;
+
+    /// The input contains a boolean.
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> where E: Error {
+        let _ = v;
+        Err(Error::custom("bool is not supported"))
+    }
+
+    /// The input contains an `i32`.
+    fn visit_i32<E>(self, v: i32) -> Result<Self::Value, E> where E: Error {
+        let _ = v;
+        Err(Error::custom("i32 is not supported"))
+    }
+
+    /// The input contains a string. The lifetime of the string is ephemeral
+    /// and it may be destroyed after this method returns.
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> where E: Error {
+        let _ = v;
+        Err(Error::custom("str is not supported"))
+    }
+
+    /// The input contains a sequence of elements.
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+        let _ = seq;
+        Err(Error::custom("seq is not supported"))
+    }
+
+    /// The input contains a homogeneous sequence of `i8` values, reported by
+    /// a `Deserializer` that distinguishes a typed `ByteArray` from a
+    /// generic sequence.
+    ///
+    /// The default implementation forwards to `visit_seq`, which is correct
+    /// for visitors that have no special handling for typed arrays.
+    fn visit_i8_seq<A>(self, seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+        self.visit_seq(seq)
+    }
+
+    /// The input contains a homogeneous sequence of `i32` values, reported
+    /// by a `Deserializer` that distinguishes a typed `IntArray` from a
+    /// generic sequence.
+    ///
+    /// See `visit_i8_seq` for the default implementation and when to
+    /// override it.
+    fn visit_i32_seq<A>(self, seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+        self.visit_seq(seq)
+    }
+
+    /// The input contains a homogeneous sequence of `i64` values, reported
+    /// by a `Deserializer` that distinguishes a typed `LongArray` from a
+    /// generic sequence.
+    ///
+    /// See `visit_i8_seq` for the default implementation and when to
+    /// override it.
+    fn visit_i64_seq<A>(self, seq: A) -> Result<Self::Value, A::Error> where A: SeqAccess<'de> {
+        self.visit_seq(seq)
+    }
+}
+
+/// Provides a `Visitor` access to each element of a sequence in the input.
+pub trait SeqAccess<'de>: Sized {
+    /// The error type that can be returned if some error occurs during
+    /// deserialization.
+    type Error: Error;
+
+    /// This returns `Ok(Some(value))` for the next value in the sequence,
+    /// or `Ok(None)` if there are no more remaining items.
+    fn next_element<T>(&mut self) -> Result<Option<T>, Self::Error> where T: Deserialize<'de>;
+}
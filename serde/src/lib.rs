@@ -0,0 +1,68 @@
+//! Serde is a framework for *ser*ializing and *de*serializing Rust data
+//! structures efficiently and generically.
+//!
+//! The Serde ecosystem consists of data structures that know how to
+//! serialize and deserialize themselves along with data formats that know
+//! how to serialize and deserialize other things. Serde provides the layer
+//! by which these two groups interact with each other, allowing any
+//! supported data structure to be serialized and deserialized using any
+//! supported data format.
+//!
+//! See the [Serde website] for additional documentation and usage examples.
+//!
+//! [Serde website]: https://serde.rs/
+
+#![doc(html_root_url = "https://docs.rs/serde/1.0.0")]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+////////////////////////////////////////////////////////////////////////////
+
+/// A facade around all the types we need from the `std`, `core`, and `alloc`
+/// crates. This avoids elaborate import wrangling having to happen in every
+/// module.
+///
+/// Not every module uses every item re-exported here, so this whole facade
+/// is exempted from the unused-imports lint rather than pruning it down to
+/// whatever the current module set happens to touch.
+#[allow(unused_imports)]
+mod lib {
+    mod core {
+        #[cfg(not(feature = "std"))]
+        pub use core::*;
+        #[cfg(feature = "std")]
+        pub use std::*;
+    }
+
+    pub use self::core::{cmp, fmt, iter, mem, num, slice, str};
+
+    pub use self::core::cell::{Cell, RefCell};
+    pub use self::core::clone::{self, Clone};
+    pub use self::core::convert::{self, From, Into};
+    pub use self::core::default::{self, Default};
+    pub use self::core::fmt::{Debug, Display};
+    pub use self::core::marker::{self, PhantomData};
+    pub use self::core::option::{self, Option};
+    pub use self::core::result::{self, Result};
+
+    #[cfg(feature = "std")]
+    pub use std::error;
+    #[cfg(feature = "std")]
+    pub use std::io::Write;
+    #[cfg(feature = "std")]
+    pub use std::vec::Vec;
+    #[cfg(feature = "std")]
+    pub use std::string::String;
+}
+
+////////////////////////////////////////////////////////////////////////////
+
+#[macro_use]
+mod macros;
+
+pub mod de;
+pub mod ser;
+
+#[doc(inline)]
+pub use de::{Deserialize, Deserializer};
+#[doc(inline)]
+pub use ser::{Serialize, Serializer};
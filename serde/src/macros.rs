@@ -0,0 +1,307 @@
+// Helper macros when implementing the Serializer part of a new data format
+// for Serde. Each group name implements one method on the Serializer trait.
+// Use these when a given data format does not support the type of that
+// method.
+
+/// Implement multiple `Serializer` trait methods for the types that a
+/// partial data format does not support.
+///
+/// Many formats only support a subset of Serde's data model -- a typed
+/// array format might only implement `serialize_i32_seq`, for instance, and
+/// have no sensible way to serialize a `bool` or a `str`. Hand-writing every
+/// other `Serializer` method to return an error is repetitive, which is why
+/// external implementations of this pattern routinely fall back to
+/// `unimplemented!()`, turning an otherwise recoverable situation into a
+/// panic.
+///
+/// `serialize_unsupported!` expands to one method per identifier passed in,
+/// each returning `Err(Error::custom(..))` instead. List the group names
+/// that your format does not support; `Impossible` remains the right choice
+/// for the *compound* associated types, since this macro only covers the
+/// scalar and compound *entry point* methods of `Serializer`.
+///
+/// ```rust
+/// # #[macro_use]
+/// # extern crate serde;
+/// #
+/// # use std::fmt;
+/// #
+/// # use serde::ser::{Impossible, Serializer};
+/// #
+/// # #[derive(Debug)]
+/// # struct Error(String);
+/// #
+/// # impl fmt::Display for Error {
+/// #     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+/// #         fmt::Display::fmt(&self.0, f)
+/// #     }
+/// # }
+/// #
+/// # impl serde::ser::Error for Error {
+/// #     fn custom<T: fmt::Display>(msg: T) -> Self {
+/// #         Error(msg.to_string())
+/// #     }
+/// # }
+/// #
+/// # struct ArraySerializer;
+/// #
+/// impl Serializer for ArraySerializer {
+///     type Ok = ();
+///     type Error = Error;
+///
+///     type SerializeSeq = Impossible<(), Error>;
+///     # type SerializeTuple = Impossible<(), Error>;
+///     # type SerializeTupleStruct = Impossible<(), Error>;
+///     # type SerializeTupleVariant = Impossible<(), Error>;
+///     # type SerializeMap = Impossible<(), Error>;
+///     # type SerializeStruct = Impossible<(), Error>;
+///     # type SerializeStructVariant = Impossible<(), Error>;
+///
+///     fn serialize_i32_seq<I>(self, values: I) -> Result<Self::Ok, Self::Error>
+///         where I: IntoIterator<Item = i32>,
+///               I::IntoIter: ExactSizeIterator
+///     {
+/// #       stringify! {
+///         /* write out the typed IntArray tag */
+/// #       };
+/// #       unimplemented!()
+///     }
+///
+///     serialize_unsupported! {
+///         bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes none some
+///         unit unit_struct unit_variant newtype_struct newtype_variant
+///         seq seq_fixed_size tuple tuple_struct tuple_variant map struct
+///         struct_variant
+///     }
+/// }
+/// #
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! serialize_unsupported {
+    ($($func:ident)*) => {
+        $(
+            serialize_unsupported_method!{$func}
+        )*
+    };
+}
+
+/// Implementation detail of `serialize_unsupported!`, kept as its own macro
+/// so each group name expands to exactly one method body.
+#[macro_export]
+macro_rules! serialize_unsupported_method {
+    (bool) => {
+        fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("bool is not supported by this format"))
+        }
+    };
+    (i8) => {
+        fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("i8 is not supported by this format"))
+        }
+    };
+    (i16) => {
+        fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("i16 is not supported by this format"))
+        }
+    };
+    (i32) => {
+        fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("i32 is not supported by this format"))
+        }
+    };
+    (i64) => {
+        fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("i64 is not supported by this format"))
+        }
+    };
+    (u8) => {
+        fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("u8 is not supported by this format"))
+        }
+    };
+    (u16) => {
+        fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("u16 is not supported by this format"))
+        }
+    };
+    (u32) => {
+        fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("u32 is not supported by this format"))
+        }
+    };
+    (u64) => {
+        fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("u64 is not supported by this format"))
+        }
+    };
+    (f32) => {
+        fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("f32 is not supported by this format"))
+        }
+    };
+    (f64) => {
+        fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("f64 is not supported by this format"))
+        }
+    };
+    (char) => {
+        fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("char is not supported by this format"))
+        }
+    };
+    (str) => {
+        fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("str is not supported by this format"))
+        }
+    };
+    (bytes) => {
+        fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+            let _ = v;
+            Err(<Self::Error as $crate::ser::Error>::custom("bytes is not supported by this format"))
+        }
+    };
+    (none) => {
+        fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+            Err(<Self::Error as $crate::ser::Error>::custom("none is not supported by this format"))
+        }
+    };
+    (some) => {
+        fn serialize_some<T: ?Sized + $crate::Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+            let _ = value;
+            Err(<Self::Error as $crate::ser::Error>::custom("some is not supported by this format"))
+        }
+    };
+    (unit) => {
+        fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+            Err(<Self::Error as $crate::ser::Error>::custom("unit is not supported by this format"))
+        }
+    };
+    (unit_struct) => {
+        fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+            let _ = name;
+            Err(<Self::Error as $crate::ser::Error>::custom("unit struct is not supported by this format"))
+        }
+    };
+    (unit_variant) => {
+        fn serialize_unit_variant(self,
+                                  name: &'static str,
+                                  variant_index: u32,
+                                  variant: &'static str)
+                                  -> Result<Self::Ok, Self::Error> {
+            let _ = name;
+            let _ = variant_index;
+            let _ = variant;
+            Err(<Self::Error as $crate::ser::Error>::custom("unit variant is not supported by this format"))
+        }
+    };
+    (newtype_struct) => {
+        fn serialize_newtype_struct<T: ?Sized + $crate::Serialize>(self,
+                                                                    name: &'static str,
+                                                                    value: &T)
+                                                                    -> Result<Self::Ok, Self::Error> {
+            let _ = name;
+            let _ = value;
+            Err(<Self::Error as $crate::ser::Error>::custom("newtype struct is not supported by this format"))
+        }
+    };
+    (newtype_variant) => {
+        fn serialize_newtype_variant<T: ?Sized + $crate::Serialize>(self,
+                                                                     name: &'static str,
+                                                                     variant_index: u32,
+                                                                     variant: &'static str,
+                                                                     value: &T)
+                                                                     -> Result<Self::Ok, Self::Error> {
+            let _ = name;
+            let _ = variant_index;
+            let _ = variant;
+            let _ = value;
+            Err(<Self::Error as $crate::ser::Error>::custom("newtype variant is not supported by this format"))
+        }
+    };
+    (seq) => {
+        fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("seq is not supported by this format"))
+        }
+    };
+    (seq_fixed_size) => {
+        fn serialize_seq_fixed_size(self, size: usize) -> Result<Self::SerializeSeq, Self::Error> {
+            let _ = size;
+            Err(<Self::Error as $crate::ser::Error>::custom("fixed size seq is not supported by this format"))
+        }
+    };
+    (tuple) => {
+        fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("tuple is not supported by this format"))
+        }
+    };
+    (tuple_struct) => {
+        fn serialize_tuple_struct(self,
+                                  name: &'static str,
+                                  len: usize)
+                                  -> Result<Self::SerializeTupleStruct, Self::Error> {
+            let _ = name;
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("tuple struct is not supported by this format"))
+        }
+    };
+    (tuple_variant) => {
+        fn serialize_tuple_variant(self,
+                                   name: &'static str,
+                                   variant_index: u32,
+                                   variant: &'static str,
+                                   len: usize)
+                                   -> Result<Self::SerializeTupleVariant, Self::Error> {
+            let _ = name;
+            let _ = variant_index;
+            let _ = variant;
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("tuple variant is not supported by this format"))
+        }
+    };
+    (map) => {
+        fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("map is not supported by this format"))
+        }
+    };
+    (struct) => {
+        fn serialize_struct(self,
+                             name: &'static str,
+                             len: usize)
+                             -> Result<Self::SerializeStruct, Self::Error> {
+            let _ = name;
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("struct is not supported by this format"))
+        }
+    };
+    (struct_variant) => {
+        fn serialize_struct_variant(self,
+                                    name: &'static str,
+                                    variant_index: u32,
+                                    variant: &'static str,
+                                    len: usize)
+                                    -> Result<Self::SerializeStructVariant, Self::Error> {
+            let _ = name;
+            let _ = variant_index;
+            let _ = variant;
+            let _ = len;
+            Err(<Self::Error as $crate::ser::Error>::custom("struct variant is not supported by this format"))
+        }
+    };
+}
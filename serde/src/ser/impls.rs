@@ -0,0 +1,40 @@
+//! This module contains `Serialize` implementations for the Rust primitive
+//! types that the rest of this crate's defaults (such as the typed array
+//! hints in `ser::Serializer`) rely on being able to serialize.
+
+use lib::*;
+
+use ser::{Serialize, Serializer};
+
+macro_rules! impl_serialize_num {
+    ($ty:ty, $method:ident) => {
+        impl Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: Serializer
+            {
+                serializer.$method(*self)
+            }
+        }
+    };
+}
+
+impl_serialize_num!(bool, serialize_bool);
+impl_serialize_num!(i8, serialize_i8);
+impl_serialize_num!(i16, serialize_i16);
+impl_serialize_num!(i32, serialize_i32);
+impl_serialize_num!(i64, serialize_i64);
+impl_serialize_num!(u8, serialize_u8);
+impl_serialize_num!(u16, serialize_u16);
+impl_serialize_num!(u32, serialize_u32);
+impl_serialize_num!(u64, serialize_u64);
+impl_serialize_num!(f32, serialize_f32);
+impl_serialize_num!(f64, serialize_f64);
+impl_serialize_num!(char, serialize_char);
+
+impl Serialize for str {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self)
+    }
+}
@@ -17,8 +17,24 @@ use ser::{self, Serialize, SerializeSeq, SerializeTuple, SerializeTupleStruct,
 /// # #[macro_use]
 /// # extern crate serde;
 /// #
+/// # use std::fmt;
+/// #
 /// # use serde::ser::{Serializer, Impossible};
-/// # use serde::private::ser::Error;
+/// #
+/// # #[derive(Debug)]
+/// # struct Error(String);
+/// #
+/// # impl fmt::Display for Error {
+/// #     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+/// #         fmt::Display::fmt(&self.0, f)
+/// #     }
+/// # }
+/// #
+/// # impl serde::ser::Error for Error {
+/// #     fn custom<T: fmt::Display>(msg: T) -> Self {
+/// #         Error(msg.to_string())
+/// #     }
+/// # }
 /// #
 /// # struct MySerializer;
 /// #
@@ -27,7 +43,12 @@ use ser::{self, Serialize, SerializeSeq, SerializeTuple, SerializeTupleStruct,
 ///     type Error = Error;
 ///
 ///     type SerializeSeq = Impossible<(), Error>;
-///     /* other associated types */
+///     # type SerializeTuple = Impossible<(), Error>;
+///     # type SerializeTupleStruct = Impossible<(), Error>;
+///     # type SerializeTupleVariant = Impossible<(), Error>;
+///     # type SerializeMap = Impossible<(), Error>;
+///     # type SerializeStruct = Impossible<(), Error>;
+///     # type SerializeStructVariant = Impossible<(), Error>;
 ///
 ///     /// This data format does not support serializing sequences.
 ///     fn serialize_seq(self,
@@ -42,7 +63,7 @@ use ser::{self, Serialize, SerializeSeq, SerializeTuple, SerializeTupleStruct,
 ///     }
 ///
 ///     /* other Serializer methods */
-/// #     __serialize_unimplemented! {
+/// #     serialize_unsupported! {
 /// #         bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str bytes none some
 /// #         unit unit_struct unit_variant newtype_struct newtype_variant
 /// #         seq_fixed_size tuple tuple_struct tuple_variant map struct
@@ -0,0 +1,199 @@
+//! This module contains `LengthBuffered`, an adapter that buffers the
+//! content of a compound value so that its length is known before a
+//! length-prefixed format has to commit to writing it.
+
+use lib::*;
+
+use ser::{self, Serialize, Serializer, SerializeSeq, SerializeTuple, SerializeMap,
+          SerializeStruct};
+
+/// Helper type for implementing compound serialization in formats that must
+/// write an element count *before* the elements themselves, such as
+/// MessagePack.
+///
+/// Serde only tells a `Serializer` the length of a sequence or map as an
+/// `Option<usize>`, and that hint is frequently `None` (for example when the
+/// value being serialized is an iterator). `LengthBuffered` works around
+/// this by serializing every element into a scratch buffer `W` as it
+/// arrives, counting them along the way, and only handing control back to
+/// the real serializer once `end()` is called and the final count is known.
+///
+/// `LengthBuffered` is generic over the scratch buffer `W` (any `Default`
+/// buffer an `ElementSerializer` knows how to write into; `W` has no default
+/// type and is always named explicitly), the `ElementSerializer` used to
+/// serialize each buffered value, and
+/// the `Finish` closure that receives the final count together with the
+/// buffer and produces the `Serializer`'s real output -- typically by
+/// writing the length prefix followed by the buffered bytes.
+///
+/// ```rust
+/// # extern crate serde;
+/// #
+/// # use std::fmt;
+/// #
+/// # use serde::ser::{LengthBuffered, Serializer};
+/// #
+/// # #[derive(Debug)]
+/// # struct Error(String);
+/// #
+/// # impl fmt::Display for Error {
+/// #     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+/// #         fmt::Display::fmt(&self.0, f)
+/// #     }
+/// # }
+/// #
+/// # impl serde::ser::Error for Error {
+/// #     fn custom<T: fmt::Display>(msg: T) -> Self {
+/// #         Error(msg.to_string())
+/// #     }
+/// # }
+/// #
+/// # struct MyElementSerializer<'a>(&'a mut Vec<u8>);
+/// #
+/// fn serialize_seq(buf: &mut Vec<u8>, len: Option<usize>)
+///     -> Result<LengthBuffered<Vec<u8>,
+///                               MyElementSerializer,
+///                               fn(&mut Vec<u8>) -> MyElementSerializer,
+///                               fn(usize, Vec<u8>) -> Result<(), Error>>,
+///               Error>
+/// {
+/// #   stringify! {
+///     Ok(LengthBuffered::new(
+///         |buf| MyElementSerializer(buf),
+///         |count, buffered| {
+///             // write the real count prefix, then the buffered bytes
+///             Ok(())
+///         },
+///     ))
+/// #   };
+/// #   unimplemented!()
+/// }
+/// #
+/// # fn main() {}
+/// ```
+pub struct LengthBuffered<W, ElementSerializer, MakeElementSerializer, Finish> {
+    buffer: W,
+    count: usize,
+    make_element_serializer: MakeElementSerializer,
+    finish: Finish,
+    _marker: PhantomData<ElementSerializer>,
+}
+
+impl<W, ElementSerializer, MakeElementSerializer, Finish>
+    LengthBuffered<W, ElementSerializer, MakeElementSerializer, Finish>
+    where W: Default
+{
+    /// Construct a new `LengthBuffered` adapter.
+    ///
+    /// `make_element_serializer` is called once per buffered element (twice
+    /// per map entry, once for the key and once for the value) to produce a
+    /// fresh `Serializer` that writes into the scratch buffer. `finish` is
+    /// called exactly once, from `end()`, with the number of buffered
+    /// elements and the buffer itself.
+    pub fn new(make_element_serializer: MakeElementSerializer, finish: Finish) -> Self {
+        LengthBuffered {
+            buffer: W::default(),
+            count: 0,
+            make_element_serializer,
+            finish,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W, ElementSerializer, MakeElementSerializer, Finish, Ok, Error> SerializeSeq
+    for LengthBuffered<W, ElementSerializer, MakeElementSerializer, Finish>
+    where ElementSerializer: Serializer<Ok = (), Error = Error>,
+          MakeElementSerializer: FnMut(&mut W) -> ElementSerializer,
+          Finish: FnOnce(usize, W) -> Result<Ok, Error>,
+          Error: ser::Error
+{
+    type Ok = Ok;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let serializer = (self.make_element_serializer)(&mut self.buffer);
+        value.serialize(serializer).map_err(|err| err.element(self.count))?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Ok, Error> {
+        (self.finish)(self.count, self.buffer)
+    }
+}
+
+impl<W, ElementSerializer, MakeElementSerializer, Finish, Ok, Error> SerializeTuple
+    for LengthBuffered<W, ElementSerializer, MakeElementSerializer, Finish>
+    where ElementSerializer: Serializer<Ok = (), Error = Error>,
+          MakeElementSerializer: FnMut(&mut W) -> ElementSerializer,
+          Finish: FnOnce(usize, W) -> Result<Ok, Error>,
+          Error: ser::Error
+{
+    type Ok = Ok;
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let serializer = (self.make_element_serializer)(&mut self.buffer);
+        value.serialize(serializer).map_err(|err| err.element(self.count))?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Ok, Error> {
+        (self.finish)(self.count, self.buffer)
+    }
+}
+
+impl<W, ElementSerializer, MakeElementSerializer, Finish, Ok, Error> SerializeMap
+    for LengthBuffered<W, ElementSerializer, MakeElementSerializer, Finish>
+    where ElementSerializer: Serializer<Ok = (), Error = Error>,
+          MakeElementSerializer: FnMut(&mut W) -> ElementSerializer,
+          Finish: FnOnce(usize, W) -> Result<Ok, Error>,
+          Error: ser::Error
+{
+    type Ok = Ok;
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Error> {
+        let serializer = (self.make_element_serializer)(&mut self.buffer);
+        key.serialize(serializer).map_err(|err| err.element(self.count))
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        let serializer = (self.make_element_serializer)(&mut self.buffer);
+        let index = self.count;
+        value.serialize(serializer).map_err(|err| err.element(index))?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Ok, Error> {
+        (self.finish)(self.count, self.buffer)
+    }
+}
+
+impl<W, ElementSerializer, MakeElementSerializer, Finish, Ok, Error> SerializeStruct
+    for LengthBuffered<W, ElementSerializer, MakeElementSerializer, Finish>
+    where ElementSerializer: Serializer<Ok = (), Error = Error>,
+          MakeElementSerializer: FnMut(&mut W) -> ElementSerializer,
+          Finish: FnOnce(usize, W) -> Result<Ok, Error>,
+          Error: ser::Error
+{
+    type Ok = Ok;
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self,
+                                               key: &'static str,
+                                               value: &T)
+                                               -> Result<(), Error> {
+        let serializer = (self.make_element_serializer)(&mut self.buffer);
+        value.serialize(serializer).map_err(|err| err.field(key))?;
+        self.count += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Ok, Error> {
+        (self.finish)(self.count, self.buffer)
+    }
+}
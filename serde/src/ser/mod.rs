@@ -0,0 +1,455 @@
+//! Generic data structure serialization framework.
+//!
+//! The two most important traits in this module are `Serialize` and
+//! `Serializer`.
+//!
+//!  - **A type that implements `Serialize` is a data structure** that can be
+//!    serialized to any data format supported by Serde, and conversely
+//!  - **A type that implements `Serializer` is a data format** that can
+//!    serialize any data structure supported by Serde.
+//!
+//! # The Serialize trait
+//!
+//! Serde provides `Serialize` implementations for many Rust primitive and
+//! standard library types. The complete list is below. All of these can be
+//! serialized using Serde out of the box.
+//!
+//! Additionally, Serde provides a derive macro to generate `Serialize`
+//! implementations for structs and enums in your own program. See the
+//! derive section of the manual for how to use this.
+//!
+//! In rare cases it may be necessary to implement `Serialize` manually for
+//! some type in your program. See the Implementing `Serialize` section of
+//! the manual for more about this.
+
+use lib::*;
+
+mod impls;
+mod impossible;
+mod length_buffered;
+
+pub use self::impossible::Impossible;
+pub use self::length_buffered::LengthBuffered;
+
+///////////////////////////////////////////////////////////////////////////
+
+/// Trait used by `Serialize` implementations to generically construct
+/// errors belonging to the `Serializer` against which they are
+/// currently running.
+///
+/// # Example implementation
+///
+/// The [example data format] presented on the website shows an error
+/// type appropriate for a basic JSON data format.
+///
+/// [example data format]: https://serde.rs/data-format.html
+pub trait Error: Sized {
+    /// Used when a `Serialize` implementation encounters any error while
+    /// serializing a type.
+    fn custom<T: Display>(msg: T) -> Self;
+
+    /// Annotate this error with the name of the struct or enum field that
+    /// was being serialized when it occurred.
+    ///
+    /// Compound serializers call this as they unwind from a failed
+    /// `serialize_field` so that a format which tracks
+    /// location can build up a path like `` field `foo` -> element 3 ``.
+    /// The default implementation returns `self` unchanged, so error types
+    /// that have no use for this information are unaffected.
+    fn field(self, name: &'static str) -> Self {
+        let _ = name;
+        self
+    }
+
+    /// Annotate this error with the index of the sequence or tuple element,
+    /// or of the map entry, that was being serialized when it occurred.
+    ///
+    /// See `field` above for how this is used and why it defaults to a
+    /// no-op.
+    fn element(self, index: usize) -> Self {
+        let _ = index;
+        self
+    }
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// A **data structure** that can be serialized into any data format
+/// supported by Serde.
+///
+/// Serde provides `Serialize` implementations for many Rust primitive and
+/// standard library types. The complete list is available in the Serde
+/// API documentation. All of these can be serialized using Serde out of
+/// the box.
+///
+/// Additionally, Serde provides a derive macro to generate `Serialize`
+/// implementations for structs and enums in your own program.
+pub trait Serialize {
+    /// Serialize this value into the given Serde serializer.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer;
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// A **data format** that can serialize any data structure supported by
+/// Serde.
+///
+/// The role of this trait is to define the serialization half of the
+/// [Serde data model], which is a way to categorize every Rust data
+/// structure into one of 29 possible types. Each method of the
+/// `Serializer` trait corresponds to one of the types of the data model.
+///
+/// [Serde data model]: https://serde.rs/data-model.html
+pub trait Serializer: Sized {
+    /// The output type produced by this `Serializer` during successful
+    /// serialization. Most serializers that produce text or binary output
+    /// should set `Ok = ()` and serialize into an `io::Write` or buffer
+    /// contained within the `Serializer` instance. Serializers that
+    /// build in-memory data structures may be simplified by using `Ok` to
+    /// propagate the data structure around.
+    type Ok;
+
+    /// The error type when some error occurs during serialization.
+    type Error: Error;
+
+    /// Type returned from `serialize_seq` and `serialize_seq_fixed_size`
+    /// for serializing the content of the sequence.
+    type SerializeSeq: SerializeSeq<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Type returned from `serialize_tuple` for serializing the content
+    /// of the tuple.
+    type SerializeTuple: SerializeTuple<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Type returned from `serialize_tuple_struct` for serializing the
+    /// content of the tuple struct.
+    type SerializeTupleStruct: SerializeTupleStruct<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Type returned from `serialize_tuple_variant` for serializing the
+    /// content of the tuple variant.
+    type SerializeTupleVariant: SerializeTupleVariant<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Type returned from `serialize_map` for serializing the content of
+    /// the map.
+    type SerializeMap: SerializeMap<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Type returned from `serialize_struct` for serializing the content
+    /// of the struct.
+    type SerializeStruct: SerializeStruct<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Type returned from `serialize_struct_variant` for serializing the
+    /// content of the struct variant.
+    type SerializeStructVariant: SerializeStructVariant<Ok = Self::Ok, Error = Self::Error>;
+
+    /// Serialize a `bool` value.
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize an `i8` value.
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize an `i16` value.
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize an `i32` value.
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize an `i64` value.
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `u8` value.
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `u16` value.
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `u32` value.
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `u64` value.
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize an `f32` value.
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize an `f64` value.
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a character.
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `&str`.
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a chunk of raw byte data.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a homogeneous sequence of `i8` values, analogous to
+    /// `serialize_bytes` but for formats like NBT that distinguish a typed
+    /// `ByteArray` from a generic sequence.
+    ///
+    /// The default implementation falls back to `serialize_seq`, which is
+    /// correct for every format but does not take advantage of the typed
+    /// hint. Formats that care should override this to emit their own tag.
+    fn serialize_i8_seq<I>(self, values: I) -> Result<Self::Ok, Self::Error>
+        where I: IntoIterator<Item = i8>,
+              I::IntoIter: ExactSizeIterator
+    {
+        let iter = values.into_iter();
+        let mut seq = self.serialize_seq(Some(iter.len()))?;
+        for (index, value) in iter.enumerate() {
+            seq.serialize_element(&value).map_err(|err| err.element(index))?;
+        }
+        seq.end()
+    }
+
+    /// Serialize a homogeneous sequence of `i32` values, analogous to
+    /// `serialize_bytes` but for formats like NBT that distinguish a typed
+    /// `IntArray` from a generic sequence.
+    ///
+    /// See `serialize_i8_seq` for the default implementation and when to
+    /// override it.
+    fn serialize_i32_seq<I>(self, values: I) -> Result<Self::Ok, Self::Error>
+        where I: IntoIterator<Item = i32>,
+              I::IntoIter: ExactSizeIterator
+    {
+        let iter = values.into_iter();
+        let mut seq = self.serialize_seq(Some(iter.len()))?;
+        for (index, value) in iter.enumerate() {
+            seq.serialize_element(&value).map_err(|err| err.element(index))?;
+        }
+        seq.end()
+    }
+
+    /// Serialize a homogeneous sequence of `i64` values, analogous to
+    /// `serialize_bytes` but for formats like NBT that distinguish a typed
+    /// `LongArray` from a generic sequence.
+    ///
+    /// See `serialize_i8_seq` for the default implementation and when to
+    /// override it.
+    fn serialize_i64_seq<I>(self, values: I) -> Result<Self::Ok, Self::Error>
+        where I: IntoIterator<Item = i64>,
+              I::IntoIter: ExactSizeIterator
+    {
+        let iter = values.into_iter();
+        let mut seq = self.serialize_seq(Some(iter.len()))?;
+        for (index, value) in iter.enumerate() {
+            seq.serialize_element(&value).map_err(|err| err.element(index))?;
+        }
+        seq.end()
+    }
+
+    /// Serialize a `None` value.
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `Some(T)` value.
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a `()` value.
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a unit struct like `struct Unit` or `PhantomData<T>`.
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a unit variant like `E::A` in `enum E { A, B }`.
+    fn serialize_unit_variant(self,
+                               name: &'static str,
+                               variant_index: u32,
+                               variant: &'static str)
+                               -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a newtype struct like `struct Millimeters(u8)`.
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self,
+                                                        name: &'static str,
+                                                        value: &T)
+                                                        -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a newtype variant like `E::N` in `enum E { N(u8) }`.
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self,
+                                                         name: &'static str,
+                                                         variant_index: u32,
+                                                         variant: &'static str,
+                                                         value: &T)
+                                                         -> Result<Self::Ok, Self::Error>;
+
+    /// Begin to serialize a variably sized sequence. This call must be
+    /// followed by zero or more calls to `serialize_element`, then a call
+    /// to `end`.
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error>;
+
+    /// Begin to serialize a statically sized sequence whose length will
+    /// be known at deserialization time without looking at the
+    /// serialized data. This call must be followed by zero or more calls
+    /// to `serialize_element`, then a call to `end`.
+    fn serialize_seq_fixed_size(self, size: usize) -> Result<Self::SerializeSeq, Self::Error>;
+
+    /// Begin to serialize a tuple. This call must be followed by zero or
+    /// more calls to `serialize_element`, then a call to `end`.
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error>;
+
+    /// Begin to serialize a tuple struct like `struct Rgb(u8, u8, u8)`.
+    fn serialize_tuple_struct(self,
+                               name: &'static str,
+                               len: usize)
+                               -> Result<Self::SerializeTupleStruct, Self::Error>;
+
+    /// Begin to serialize a tuple variant like `E::T` in `enum E { T(u8, u8) }`.
+    fn serialize_tuple_variant(self,
+                                name: &'static str,
+                                variant_index: u32,
+                                variant: &'static str,
+                                len: usize)
+                                -> Result<Self::SerializeTupleVariant, Self::Error>;
+
+    /// Begin to serialize a map. This call must be followed by zero or
+    /// more calls to `serialize_key` and `serialize_value`, then a call
+    /// to `end`.
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error>;
+
+    /// Begin to serialize a struct like `struct Rgb { r: u8, g: u8, b: u8 }`.
+    fn serialize_struct(self,
+                         name: &'static str,
+                         len: usize)
+                         -> Result<Self::SerializeStruct, Self::Error>;
+
+    /// Begin to serialize a struct variant like `E::S` in
+    /// `enum E { S { r: u8, g: u8, b: u8 } }`.
+    fn serialize_struct_variant(self,
+                                 name: &'static str,
+                                 variant_index: u32,
+                                 variant: &'static str,
+                                 len: usize)
+                                 -> Result<Self::SerializeStructVariant, Self::Error>;
+}
+
+///////////////////////////////////////////////////////////////////////////
+
+/// Returned from `Serializer::serialize_seq` and
+/// `Serializer::serialize_seq_fixed_size`.
+///
+/// Implementations that track where in the input an error occurred may call
+/// `Error::element` on an error bubbling up from `serialize_element` to
+/// attach the index that was being serialized.
+pub trait SerializeSeq {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a sequence element.
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error>;
+
+    /// Finish serializing a sequence.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+}
+
+/// Returned from `Serializer::serialize_tuple`.
+pub trait SerializeTuple {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a tuple element.
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error>;
+
+    /// Finish serializing a tuple.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+}
+
+/// Returned from `Serializer::serialize_tuple_struct`.
+pub trait SerializeTupleStruct {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a tuple struct field.
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error>;
+
+    /// Finish serializing a tuple struct.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+}
+
+/// Returned from `Serializer::serialize_tuple_variant`.
+pub trait SerializeTupleVariant {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a tuple variant field.
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error>;
+
+    /// Finish serializing a tuple variant.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+}
+
+/// Returned from `Serializer::serialize_map`.
+pub trait SerializeMap {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a map key.
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error>;
+
+    /// Serialize a map value.
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error>;
+
+    /// Finish serializing a map.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+
+    /// Serialize a map entry consisting of a key and a value.
+    fn serialize_entry<K: ?Sized + Serialize, V: ?Sized + Serialize>(&mut self,
+                                                                      key: &K,
+                                                                      value: &V)
+                                                                      -> Result<(), Self::Error> {
+        self.serialize_key(key)?;
+        self.serialize_value(value)
+    }
+}
+
+/// Returned from `Serializer::serialize_struct`.
+///
+/// Implementations that track where in the input an error occurred may call
+/// `Error::field` on an error bubbling up from `serialize_field` to attach
+/// the name of the field that was being serialized.
+pub trait SerializeStruct {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a struct field.
+    fn serialize_field<T: ?Sized + Serialize>(&mut self,
+                                               key: &'static str,
+                                               value: &T)
+                                               -> Result<(), Self::Error>;
+
+    /// Finish serializing a struct.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+}
+
+/// Returned from `Serializer::serialize_struct_variant`.
+pub trait SerializeStructVariant {
+    /// Must match the `Ok` type of our `Serializer`.
+    type Ok;
+
+    /// Must match the `Error` type of our `Serializer`.
+    type Error: Error;
+
+    /// Serialize a struct variant field.
+    fn serialize_field<T: ?Sized + Serialize>(&mut self,
+                                               key: &'static str,
+                                               value: &T)
+                                               -> Result<(), Self::Error>;
+
+    /// Finish serializing a struct variant.
+    fn end(self) -> Result<Self::Ok, Self::Error>;
+}